@@ -0,0 +1,99 @@
+use std::fmt;
+
+/// The two trust levels a session can run under. A session starts
+/// `Proctored` and can only escalate to `Admin` via `unlock_admin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Proctored,
+    Admin,
+}
+
+/// A single permission a command can require before it acts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    FileRead,
+    FileWrite,
+    TerminalWrite,
+    Exit,
+    ViewLog,
+}
+
+impl Role {
+    fn granted(self) -> &'static [Capability] {
+        match self {
+            Role::Proctored => &[
+                Capability::FileRead,
+                Capability::FileWrite,
+                Capability::TerminalWrite,
+                Capability::ViewLog,
+            ],
+            Role::Admin => &[
+                Capability::FileRead,
+                Capability::FileWrite,
+                Capability::TerminalWrite,
+                Capability::Exit,
+                Capability::ViewLog,
+            ],
+        }
+    }
+
+    pub fn grants(self, capability: Capability) -> bool {
+        self.granted().contains(&capability)
+    }
+
+    /// Returns `Ok(())` if this role has `capability`, otherwise a typed denial.
+    pub fn require(self, capability: Capability) -> Result<(), CapabilityDenied> {
+        if self.grants(capability) {
+            Ok(())
+        } else {
+            Err(CapabilityDenied { role: self, capability })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CapabilityDenied {
+    role: Role,
+    capability: Capability,
+}
+
+impl fmt::Display for CapabilityDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Role {:?} does not have capability {:?}", self.role, self.capability)
+    }
+}
+
+impl std::error::Error for CapabilityDenied {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proctored_can_read_and_write_files_but_not_exit() {
+        assert!(Role::Proctored.grants(Capability::FileRead));
+        assert!(Role::Proctored.grants(Capability::FileWrite));
+        assert!(Role::Proctored.grants(Capability::TerminalWrite));
+        assert!(Role::Proctored.grants(Capability::ViewLog));
+        assert!(!Role::Proctored.grants(Capability::Exit));
+    }
+
+    #[test]
+    fn admin_has_every_capability() {
+        for capability in [
+            Capability::FileRead,
+            Capability::FileWrite,
+            Capability::TerminalWrite,
+            Capability::Exit,
+            Capability::ViewLog,
+        ] {
+            assert!(Role::Admin.grants(capability));
+        }
+    }
+
+    #[test]
+    fn require_denies_an_ungranted_capability() {
+        assert!(Role::Proctored.require(Capability::Exit).is_err());
+        assert!(Role::Admin.require(Capability::Exit).is_ok());
+    }
+}
@@ -0,0 +1,237 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Default `.proctorignore` content seeded the first time a session runs,
+/// keeping the old hardcoded editor temp-file skips (`*.swp`, `*~`) as a
+/// sane starting point.
+const DEFAULT_PROCTORIGNORE: &str = "\
+# Editor temp files
+*.swp
+*~
+
+# Common build/VCS noise
+node_modules/
+target/
+.git/
+";
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+    segments: Vec<String>,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let mut pattern = line;
+        let negated = pattern.starts_with('!');
+        if negated {
+            pattern = &pattern[1..];
+        }
+
+        let anchored = pattern.starts_with('/');
+        if anchored {
+            pattern = &pattern[1..];
+        }
+
+        let dir_only = pattern.ends_with('/') && pattern.len() > 1;
+        let pattern = if dir_only { &pattern[..pattern.len() - 1] } else { pattern };
+
+        let segments = pattern.split('/').map(String::from).collect();
+        Some(IgnoreRule { negated, dir_only, anchored, segments })
+    }
+}
+
+/// Evaluates watched paths against a `.proctorignore` file using gitignore
+/// semantics (last matching pattern wins, `!` re-includes, `**` crosses
+/// segments). Patterns are compiled once on load and re-parsed only when
+/// the backing file changes.
+pub struct IgnoreMatcher {
+    path: PathBuf,
+    rules: Mutex<Vec<IgnoreRule>>,
+}
+
+impl IgnoreMatcher {
+    /// Loads `path`, creating it with [`DEFAULT_PROCTORIGNORE`] if it doesn't exist yet.
+    pub fn load(path: PathBuf) -> Self {
+        if !path.exists() {
+            let _ = fs::write(&path, DEFAULT_PROCTORIGNORE);
+        }
+        let rules = Mutex::new(Self::parse_file(&path));
+        IgnoreMatcher { path, rules }
+    }
+
+    fn parse_file(path: &Path) -> Vec<IgnoreRule> {
+        fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(IgnoreRule::parse)
+            .collect()
+    }
+
+    /// Re-reads the `.proctorignore` file from disk. Call this when the
+    /// watcher observes a change to it.
+    pub fn reload(&self) {
+        *self.rules.lock().unwrap() = Self::parse_file(&self.path);
+    }
+
+    pub fn watched_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true if `relative_path` (relative to the workspace root)
+    /// should be excluded from log-event emission.
+    pub fn is_ignored(&self, relative_path: &Path, is_dir: bool) -> bool {
+        let segments: Vec<String> = relative_path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        if segments.is_empty() {
+            return false;
+        }
+
+        let rules = self.rules.lock().unwrap();
+        let mut ignored = false;
+        for rule in rules.iter() {
+            if Self::rule_matches(&rule.segments, &segments, rule.anchored, rule.dir_only, is_dir) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+
+    /// True if `pattern` matches `path` starting anywhere permitted by
+    /// `anchored`. A match doesn't have to consume the whole path: once a
+    /// directory pattern matches a leading prefix of `path`, everything
+    /// beneath it is implicitly covered too (standard gitignore recursive-
+    /// descent behavior) - so `node_modules/` also matches
+    /// `node_modules/lodash/index.js`. `dir_only` is only enforced when the
+    /// match reaches the literal last segment of `path`; a match that stops
+    /// at an ancestor directory satisfies it unconditionally, since that
+    /// ancestor must have been a directory to have `path`'s remainder inside it.
+    fn rule_matches(pattern: &[String], path: &[String], anchored: bool, dir_only: bool, is_dir: bool) -> bool {
+        let starts: Box<dyn Iterator<Item = usize>> = if anchored {
+            Box::new(std::iter::once(0))
+        } else {
+            // An unanchored pattern may match starting at any path segment,
+            // e.g. `node_modules/` matches `src/node_modules`.
+            Box::new(0..path.len())
+        };
+
+        for start in starts {
+            if let Some(consumed) = Self::match_segments(pattern, &path[start..]) {
+                let reaches_leaf = start + consumed == path.len();
+                if dir_only && reaches_leaf && !is_dir {
+                    continue;
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Matches `pattern` against a prefix of `path`, returning how many
+    /// segments of `path` it consumed, or `None` if it doesn't match at
+    /// all. Reaching the end of `pattern` always succeeds (consuming zero
+    /// more segments) so a directory pattern also matches its descendants.
+    fn match_segments(pattern: &[String], path: &[String]) -> Option<usize> {
+        match pattern.first() {
+            None => Some(0),
+            Some(p) if p == "**" => {
+                if pattern.len() == 1 {
+                    return Some(path.len());
+                }
+                (0..=path.len()).find_map(|skip| {
+                    Self::match_segments(&pattern[1..], &path[skip..]).map(|rest| skip + rest)
+                })
+            }
+            Some(p) => match path.first() {
+                Some(seg) if Self::glob_match(p.as_bytes(), seg.as_bytes()) => {
+                    Self::match_segments(&pattern[1..], &path[1..]).map(|rest| 1 + rest)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => (0..=text.len()).any(|i| Self::glob_match(&pattern[1..], &text[i..])),
+            (Some(b'?'), Some(_)) => Self::glob_match(&pattern[1..], &text[1..]),
+            (Some(pc), Some(tc)) if pc == tc => Self::glob_match(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn matcher_for(contents: &str) -> IgnoreMatcher {
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("proctorignore-test-{}-{}", std::process::id(), id));
+        fs::write(&path, contents).unwrap();
+        IgnoreMatcher::load(path)
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_nested_directory() {
+        let matcher = matcher_for("node_modules/\n");
+        assert!(matcher.is_ignored(Path::new("node_modules"), true));
+        assert!(matcher.is_ignored(Path::new("src/node_modules"), true));
+    }
+
+    #[test]
+    fn dir_only_pattern_ignores_descendants_regardless_of_leaf_kind() {
+        let matcher = matcher_for("node_modules/\n");
+        // The leaf being a file (not a dir) shouldn't matter once the match
+        // reaches an ancestor directory rather than the literal leaf.
+        assert!(matcher.is_ignored(Path::new("node_modules/lodash/index.js"), false));
+    }
+
+    #[test]
+    fn dir_only_pattern_does_not_match_a_same_named_file() {
+        let matcher = matcher_for("target/\n");
+        assert!(!matcher.is_ignored(Path::new("target"), false));
+    }
+
+    #[test]
+    fn anchored_pattern_only_matches_at_root() {
+        let matcher = matcher_for("/build\n");
+        assert!(matcher.is_ignored(Path::new("build"), true));
+        assert!(!matcher.is_ignored(Path::new("src/build"), true));
+    }
+
+    #[test]
+    fn negation_reincludes_in_file_order() {
+        let matcher = matcher_for("*.log\n!keep.log\n");
+        assert!(matcher.is_ignored(Path::new("debug.log"), false));
+        assert!(!matcher.is_ignored(Path::new("keep.log"), false));
+    }
+
+    #[test]
+    fn later_rule_overrides_earlier_negation() {
+        let matcher = matcher_for("*.log\n!keep.log\nkeep.log\n");
+        assert!(matcher.is_ignored(Path::new("keep.log"), false));
+    }
+
+    #[test]
+    fn double_star_crosses_segments() {
+        let matcher = matcher_for("**/*.swp\n");
+        assert!(matcher.is_ignored(Path::new("a/b/c.swp"), false));
+        assert!(matcher.is_ignored(Path::new("c.swp"), false));
+    }
+}
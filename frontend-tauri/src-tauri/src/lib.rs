@@ -1,52 +1,100 @@
+mod capabilities;
+mod ignore_rules;
 mod pty_manager;
+mod session_log;
+mod workspace_fs;
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use tauri::{AppHandle, Manager, State, WindowEvent, Emitter};
-use crate::pty_manager::{spawn_pty, PtyInstance};
+use crate::capabilities::{Capability, Role};
+use crate::ignore_rules::IgnoreMatcher;
+use crate::pty_manager::{spawn_pty, PtyInstance, PtySignal};
+use crate::session_log::{SessionLog, VerifyResult};
+use crate::workspace_fs::{FileEntry, WorkspaceFs};
 use notify::{Watcher, RecursiveMode, EventKind};
+use portable_pty::PtySize;
+
+/// Seeded into `.admin_key` the first time a session runs. The proctor
+/// should replace it, since from then on the file - not this constant - is
+/// the source of truth.
+const DEFAULT_ADMIN_KEY: &str = "1915";
 
 pub struct AppState {
     pub ptys: HashMap<String, PtyInstance>,
-    pub is_session_active: bool,
+    pub role: Role,
+    pub admin_key: String,
+    pub workspace_fs: WorkspaceFs,
+    pub session_log: Arc<SessionLog>,
 }
 
 #[tauri::command]
-fn write_to_pty(state: State<'_, Mutex<AppState>>, pty_id: String, data: Vec<u8>) {
+fn write_to_pty(state: State<'_, Mutex<AppState>>, pty_id: String, data: Vec<u8>) -> Result<(), String> {
     let state = state.lock().unwrap();
+    state.role.require(Capability::TerminalWrite).map_err(|e| e.to_string())?;
     if let Some(pty) = state.ptys.get(&pty_id) {
         let mut writer = pty.writer.lock().unwrap();
         let _ = writer.write_all(&data);
         let _ = writer.flush();
     }
+    Ok(())
+}
+
+#[tauri::command]
+fn resize_pty(state: State<'_, Mutex<AppState>>, pty_id: String, rows: u16, cols: u16, pixel_width: u16, pixel_height: u16) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::TerminalWrite).map_err(|e| e.to_string())?;
+    let pty = state.ptys.get(&pty_id).ok_or_else(|| format!("Unknown pty '{}'", pty_id))?;
+    let master = pty.master.lock().unwrap();
+    master
+        .resize(PtySize { rows, cols, pixel_width, pixel_height })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn signal_pty(state: State<'_, Mutex<AppState>>, pty_id: String, signal: String) -> Result<(), String> {
+    let signal = match signal.as_str() {
+        "SIGINT" => PtySignal::Sigint,
+        "SIGTERM" => PtySignal::Sigterm,
+        "SIGKILL" => PtySignal::Sigkill,
+        other => return Err(format!("Unsupported signal '{}'", other)),
+    };
+
+    let state = state.lock().unwrap();
+    state.role.require(Capability::TerminalWrite).map_err(|e| e.to_string())?;
+    let pty = state.ptys.get(&pty_id).ok_or_else(|| format!("Unknown pty '{}'", pty_id))?;
+    pty.signal(signal).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn verify_admin_key(state: State<'_, Mutex<AppState>>, admin_key: String) -> bool {
-    if admin_key == "1915" {
-        let mut state = state.lock().unwrap();
-        state.is_session_active = false;
-        return true;
+fn unlock_admin(state: State<'_, Mutex<AppState>>, admin_key: String) -> bool {
+    let mut state = state.lock().unwrap();
+    let success = admin_key == state.admin_key;
+    if success {
+        state.role = Role::Admin;
     }
-    false
+    let _ = state.session_log.append(serde_json::json!({
+        "type": "admin_unlock",
+        "success": success
+    }));
+    success
 }
 
 #[tauri::command]
-fn exit_app(app_handle: AppHandle) {
+fn exit_app(state: State<'_, Mutex<AppState>>, app_handle: AppHandle) -> Result<(), String> {
+    state.lock().unwrap().role.require(Capability::Exit).map_err(|e| e.to_string())?;
     app_handle.exit(0);
+    Ok(())
 }
 
 #[tauri::command]
-fn save_log(log_content: String) -> Result<(), String> {
-    let home_dir = std::env::var("HOME").map_err(|_| "Failed to get HOME directory")?;
-    let workspace_path = PathBuf::from(home_dir).join(".proctor_workspace");
-    let log_path = workspace_path.join("session_log.txt");
-    
-    fs::write(log_path, log_content).map_err(|e| e.to_string())?;
-    Ok(())
+fn verify_session_log(state: State<'_, Mutex<AppState>>) -> Result<VerifyResult, String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::ViewLog).map_err(|e| e.to_string())?;
+    state.session_log.verify().map_err(|e| e.to_string())
 }
 
 fn get_last_line(path: &PathBuf) -> Option<String> {
@@ -58,70 +106,52 @@ fn get_last_line(path: &PathBuf) -> Option<String> {
 }
 
 #[tauri::command]
-fn list_files() -> Result<Vec<String>, String> {
-    let home_dir = std::env::var("HOME").map_err(|_| "Failed to get HOME directory")?;
-    let workspace_path = PathBuf::from(home_dir).join(".proctor_workspace");
-    
-    let mut files = Vec::new();
-    if let Ok(entries) = fs::read_dir(workspace_path) {
-        for entry in entries.flatten() {
-            if let Ok(meta) = entry.metadata() {
-                if meta.is_file() {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    // Don't list the session log
-                    if name != "session_log.txt" && !name.starts_with('.') {
-                        files.push(name);
-                    }
-                }
-            }
-        }
-    }
-    files.sort();
-    Ok(files)
+fn list_files(state: State<'_, Mutex<AppState>>, path: String) -> Result<Vec<FileEntry>, String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileRead).map_err(|e| e.to_string())?;
+    state.workspace_fs.list(&path).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn read_file(name: String) -> Result<String, String> {
-    let home_dir = std::env::var("HOME").map_err(|_| "Failed to get HOME directory")?;
-    let workspace_path = PathBuf::from(home_dir).join(".proctor_workspace");
-    let file_path = workspace_path.join(name);
-    
-    // Security check: ensure file is inside workspace
-    if !file_path.starts_with(workspace_path) {
-        return Err("Access denied".into());
-    }
-
-    fs::read_to_string(file_path).map_err(|e| e.to_string())
+fn read_file(state: State<'_, Mutex<AppState>>, path: String) -> Result<String, String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileRead).map_err(|e| e.to_string())?;
+    state.workspace_fs.read(&path).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn write_file(name: String, content: String) -> Result<(), String> {
-    let home_dir = std::env::var("HOME").map_err(|_| "Failed to get HOME directory")?;
-    let workspace_path = PathBuf::from(home_dir).join(".proctor_workspace");
-    let file_path = workspace_path.join(name);
-    
-    if !file_path.starts_with(&workspace_path) {
-        return Err("Access denied".into());
-    }
+fn write_file(state: State<'_, Mutex<AppState>>, path: String, content: String) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileWrite).map_err(|e| e.to_string())?;
+    state.workspace_fs.write(&path, &content).map_err(|e| e.to_string())
+}
 
-    fs::write(file_path, content).map_err(|e| e.to_string())
+#[tauri::command]
+fn create_file(state: State<'_, Mutex<AppState>>, path: String) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileWrite).map_err(|e| e.to_string())?;
+    state.workspace_fs.create(&path).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn create_file(name: String) -> Result<(), String> {
-    let home_dir = std::env::var("HOME").map_err(|_| "Failed to get HOME directory")?;
-    let workspace_path = PathBuf::from(home_dir).join(".proctor_workspace");
-    let file_path = workspace_path.join(name);
-    
-    if !file_path.starts_with(&workspace_path) {
-        return Err("Access denied".into());
-    }
+fn create_directory(state: State<'_, Mutex<AppState>>, path: String) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileWrite).map_err(|e| e.to_string())?;
+    state.workspace_fs.mkdir(&path).map_err(|e| e.to_string())
+}
 
-    if file_path.exists() {
-        return Err("File already exists".into());
-    }
+#[tauri::command]
+fn delete_entry(state: State<'_, Mutex<AppState>>, path: String) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileWrite).map_err(|e| e.to_string())?;
+    state.workspace_fs.remove(&path).map_err(|e| e.to_string())
+}
 
-    fs::write(file_path, "").map_err(|e| e.to_string())
+#[tauri::command]
+fn rename_entry(state: State<'_, Mutex<AppState>>, from: String, to: String) -> Result<(), String> {
+    let state = state.lock().unwrap();
+    state.role.require(Capability::FileWrite).map_err(|e| e.to_string())?;
+    state.workspace_fs.rename(&from, &to).map_err(|e| e.to_string())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -144,27 +174,50 @@ pub fn run() {
                 fs::create_dir_all(&internal_path).expect("Failed to create internal directory");
             }
             
-            // Clear previous session logs
+            // Clear the previous session's command history
             let cmd_history_path = internal_path.join(".cmd_history");
-            let session_log_path = workspace_path.join("session_log.txt");
             let _ = fs::write(&cmd_history_path, "");
-            let _ = fs::write(&session_log_path, "");
-            
+
+            // Load (or seed) the admin key; the file is the source of truth from here on
+            let admin_key_path = internal_path.join(".admin_key");
+            if !admin_key_path.exists() {
+                fs::write(&admin_key_path, DEFAULT_ADMIN_KEY).expect("Failed to seed admin key");
+            }
+            let admin_key = fs::read_to_string(&admin_key_path)
+                .expect("Failed to read admin key")
+                .trim()
+                .to_string();
+
+            let session_log = Arc::new(
+                SessionLog::open(internal_path.join("audit_log.jsonl")).expect("Failed to open session audit log"),
+            );
+
             let workspace_path_clone = workspace_path.clone();
             let internal_path_clone = internal_path.clone();
             let app_handle_watcher = app_handle.clone();
-            
+            let session_log_watcher = Arc::clone(&session_log);
+
             // Initialize history size to ignore existing content
             let initial_history_size = fs::metadata(internal_path.join(".cmd_history"))
                 .map(|m| m.len())
                 .unwrap_or(0);
 
+            let ignore_matcher = IgnoreMatcher::load(internal_path.join(".proctorignore"));
+
             // Watcher Thread
+            //
+            // Diagnostics below go to eprintln! rather than the `log` crate.
+            // log::error! is a no-op without a registered backend, and
+            // adding one (env_logger, tauri-plugin-log) means a new
+            // dependency this project doesn't currently have - pulling that
+            // in deserves its own change, not a drive-by inside an
+            // unrelated watcher fix. eprintln! is the intentional choice
+            // here until that happens, not a half-finished migration.
             std::thread::spawn(move || {
                 let (tx, rx) = std::sync::mpsc::channel();
                 let mut watcher = notify::recommended_watcher(tx).unwrap();
                 let mut last_history_size = initial_history_size;
-                
+
                 if let Err(e) = watcher.watch(&workspace_path_clone, RecursiveMode::Recursive) {
                     eprintln!("Watcher error (workspace): {:?}", e);
                 }
@@ -177,11 +230,6 @@ pub fn run() {
                         Ok(event) => {
                             for path in event.paths {
                                 let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-                                
-                                // Ignore vim swap files and the log file itself
-                                if file_name.ends_with(".swp") || file_name.ends_with("~") || file_name == "session_log.txt" {
-                                    continue;
-                                }
 
                                 if path.starts_with(&internal_path_clone) && file_name == ".cmd_history" {
                                     // Command Logged - Only if file grew (prevents double logging from multiple Modify events)
@@ -189,14 +237,19 @@ pub fn run() {
                                         let current_size = meta.len();
                                         if current_size > last_history_size {
                                             if let Some(cmd) = get_last_line(&path) {
-                                                let _ = app_handle_watcher.emit("log-event", serde_json::json!({
+                                                let payload = serde_json::json!({
                                                     "type": "command",
                                                     "message": cmd
-                                                }));
+                                                });
+                                                let _ = session_log_watcher.append(payload.clone());
+                                                let _ = app_handle_watcher.emit("log-event", payload);
                                             }
                                             last_history_size = current_size;
                                         }
                                     }
+                                } else if path == ignore_matcher.watched_path() {
+                                    // The ignore rules themselves changed - recompile the matcher
+                                    ignore_matcher.reload();
                                 } else if path.starts_with(&workspace_path_clone) {
                                     // File Change in Workspace
                                     let kind_str = match event.kind {
@@ -205,11 +258,22 @@ pub fn run() {
                                         EventKind::Remove(_) => "Deleted",
                                         _ => continue,
                                     };
-                                    
-                                    let _ = app_handle_watcher.emit("log-event", serde_json::json!({
+
+                                    let is_dir = path.is_dir();
+                                    let relative_path = path
+                                        .strip_prefix(&workspace_path_clone)
+                                        .unwrap_or(&path);
+
+                                    if ignore_matcher.is_ignored(relative_path, is_dir) {
+                                        continue;
+                                    }
+
+                                    let payload = serde_json::json!({
                                         "type": "file",
                                         "message": format!("{} file '{}'", kind_str, file_name)
-                                    }));
+                                    });
+                                    let _ = session_log_watcher.append(payload.clone());
+                                    let _ = app_handle_watcher.emit("log-event", payload);
                                 }
                             }
                         },
@@ -258,16 +322,23 @@ pub fn run() {
             };
 
             ptys.insert("terminal".to_string(), spawn_pty(
-                app_handle.clone(), 
-                "terminal".to_string(), 
+                app_handle.clone(),
+                "terminal".to_string(),
                 "sh",
                 &["-c", &shell_cmd],
-                workspace_path.clone()
+                workspace_path.clone(),
+                Arc::clone(&session_log),
             ));
-            
-            app.manage(Mutex::new(AppState { 
+
+            let workspace_fs = WorkspaceFs::new(workspace_path.clone())
+                .expect("Failed to initialize workspace filesystem");
+
+            app.manage(Mutex::new(AppState {
                 ptys,
-                is_session_active: true 
+                role: Role::Proctored,
+                admin_key,
+                workspace_fs,
+                session_log,
             }));
 
             Ok(())
@@ -280,7 +351,10 @@ pub fn run() {
                 let _ = window.emit("attempted-close", ());
             }
         })
-        .invoke_handler(tauri::generate_handler![write_to_pty, verify_admin_key, exit_app, save_log, list_files, read_file, write_file, create_file])
+        .invoke_handler(tauri::generate_handler![
+            write_to_pty, resize_pty, signal_pty, unlock_admin, exit_app, verify_session_log,
+            list_files, read_file, write_file, create_file, create_directory, delete_entry, rename_entry
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
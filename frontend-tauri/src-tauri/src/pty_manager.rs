@@ -1,16 +1,78 @@
 use std::{
     io::{Read, Write},
+    path::PathBuf,
     sync::{Arc, Mutex},
     thread,
 };
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use portable_pty::{Child, CommandBuilder, MasterPty, NativePtySystem, PtySize, PtySystem};
 use tauri::{AppHandle, Emitter};
 
+use crate::session_log::SessionLog;
+
+/// Control signals the proctor UI can deliver to a running PTY child.
+#[derive(Debug, Clone, Copy)]
+pub enum PtySignal {
+    Sigint,
+    Sigterm,
+    Sigkill,
+}
+
+impl PtySignal {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            PtySignal::Sigint => libc::SIGINT,
+            PtySignal::Sigterm => libc::SIGTERM,
+            PtySignal::Sigkill => libc::SIGKILL,
+        }
+    }
+}
+
 pub struct PtyInstance {
     pub writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    pub master: Arc<Mutex<Box<dyn MasterPty + Send>>>,
+    child: Arc<Mutex<Box<dyn Child + Send>>>,
+}
+
+impl PtyInstance {
+    /// Delivers `signal` to the PTY's *current foreground* process group,
+    /// not the shell's own group. The shell (`sh`/`bash`/`zsh`) runs with
+    /// job control enabled, so once it starts a student's command it makes
+    /// that command the foreground process group of the terminal and steps
+    /// aside; `process_group_leader()` reports whichever group currently
+    /// holds the foreground, mirroring what a real terminal driver does
+    /// when it delivers Ctrl-C/Ctrl-\ to "whatever is running in front",
+    /// rather than to the session leader. This is what lets us stop a
+    /// runaway command without also killing the shell underneath it.
+    pub fn signal(&self, signal: PtySignal) -> std::io::Result<()> {
+        let pgid = self
+            .master
+            .lock()
+            .unwrap()
+            .process_group_leader()
+            .map(|pgid| pgid as u32)
+            .or_else(|| {
+                let child = self.child.lock().unwrap();
+                child.process_id()
+            })
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "process has already exited"))?;
+
+        let ret = unsafe { libc::killpg(pgid as libc::pid_t, signal.as_raw()) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
 }
 
-pub fn spawn_pty(app_handle: AppHandle, pty_id: String, command: &str, args: &[&str]) -> PtyInstance {
+pub fn spawn_pty(
+    app_handle: AppHandle,
+    pty_id: String,
+    command: &str,
+    args: &[&str],
+    cwd: PathBuf,
+    session_log: Arc<SessionLog>,
+) -> PtyInstance {
     let pty_system = NativePtySystem::default();
 
     let pair = pty_system
@@ -24,7 +86,8 @@ pub fn spawn_pty(app_handle: AppHandle, pty_id: String, command: &str, args: &[&
 
     let mut cmd = CommandBuilder::new(command);
     cmd.args(args);
-    
+    cmd.cwd(cwd);
+
     // Set TERM environment variable for coloring and proper behavior
     cmd.env("TERM", "xterm-256color");
     cmd.env("COLORTERM", "truecolor");
@@ -36,12 +99,17 @@ pub fn spawn_pty(app_handle: AppHandle, pty_id: String, command: &str, args: &[&
 
     let reader = pair.master.try_clone_reader().expect("failed to clone reader");
     let writer = pair.master.take_writer().expect("failed to take writer");
-    
+    let master = Arc::new(Mutex::new(pair.master));
+
     let writer = Arc::new(Mutex::new(writer));
     let writer_clone = Arc::clone(&writer);
 
+    let child: Arc<Mutex<Box<dyn Child + Send>>> = Arc::new(Mutex::new(child));
+    let child_clone = Arc::clone(&child);
+
     let pty_id_clone = pty_id.clone();
-    
+    let app_handle_exit = app_handle.clone();
+
     // Read thread
     thread::spawn(move || {
         let mut reader = reader;
@@ -62,10 +130,20 @@ pub fn spawn_pty(app_handle: AppHandle, pty_id: String, command: &str, args: &[&
         }
     });
 
-    // Handle child exit
+    // Handle child exit: record it in the tamper-evident audit log and
+    // report it back to the frontend
+    let pty_id_exit = pty_id.clone();
     thread::spawn(move || {
-        let _ = child.wait();
+        let status = child_clone.lock().unwrap().wait();
+        let exit_code = status.map(|s| s.exit_code()).unwrap_or(1);
+        let payload = serde_json::json!({
+            "type": "pty_exit",
+            "pty_id": pty_id_exit,
+            "exit_code": exit_code
+        });
+        let _ = session_log.append(payload.clone());
+        let _ = app_handle_exit.emit("pty-exit", payload);
     });
 
-    PtyInstance { writer: writer_clone }
+    PtyInstance { writer: writer_clone, master, child }
 }
@@ -0,0 +1,284 @@
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 64 hex chars = 32 all-zero bytes, matching a SHA-256 digest width.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Result of [`SessionLog::verify`]: whether the chain is intact, and if
+/// not, the zero-based line index where it first breaks.
+#[derive(Debug, Serialize)]
+pub struct VerifyResult {
+    pub valid: bool,
+    pub broken_at: Option<u64>,
+    pub records_checked: u64,
+}
+
+struct ChainState {
+    seq: u64,
+    prev_hash: String,
+}
+
+/// An append-only, hash-chained JSONL audit log. Every record embeds the
+/// hash of the one before it, so editing or reordering any line breaks the
+/// chain from that point on - detectable by [`Self::verify`] without needing
+/// a separate signature or external ledger. Deleting trailing lines leaves
+/// the remaining chain intact; [`Self::verify`] catches that only while the
+/// instance that wrote them is still alive.
+pub struct SessionLog {
+    path: PathBuf,
+    state: Mutex<ChainState>,
+}
+
+impl SessionLog {
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let state = Self::tail_state(&path)?;
+        Ok(SessionLog { path, state: Mutex::new(state) })
+    }
+
+    fn tail_state(path: &PathBuf) -> io::Result<ChainState> {
+        if !path.exists() {
+            return Ok(ChainState { seq: 0, prev_hash: GENESIS_HASH.to_string() });
+        }
+
+        let reader = BufReader::new(File::open(path)?);
+        let mut last: Option<Value> = None;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            last = serde_json::from_str(&line).ok();
+        }
+
+        Ok(match last {
+            Some(record) => ChainState {
+                seq: record["seq"].as_u64().unwrap_or(0),
+                prev_hash: record["hash"].as_str().unwrap_or(GENESIS_HASH).to_string(),
+            },
+            None => ChainState { seq: 0, prev_hash: GENESIS_HASH.to_string() },
+        })
+    }
+
+    /// Appends `payload` as the next record in the chain.
+    pub fn append(&self, payload: Value) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let seq = state.seq + 1;
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let hash = Self::compute_hash(&state.prev_hash, seq, timestamp_ms, &payload);
+        let record = serde_json::json!({
+            "seq": seq,
+            "timestamp_ms": timestamp_ms,
+            "payload": payload,
+            "prev_hash": state.prev_hash,
+            "hash": hash,
+        });
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", record)?;
+
+        state.seq = seq;
+        state.prev_hash = hash;
+        Ok(())
+    }
+
+    /// Walks the file from the start, recomputing each record's hash and
+    /// checking it chains from the previous one, reporting the first
+    /// record (if any) where that breaks. Also compares the final
+    /// recomputed hash against the live in-memory [`ChainState`], so
+    /// deleting trailing lines out from under a still-running process (the
+    /// realistic window before a proctor reviews the log) is caught too,
+    /// not just tampering with a line that's still present. This can't
+    /// catch truncation that happens after the process holding `self.state`
+    /// has exited - that needs an external anchor (e.g. a proctor-side copy
+    /// of the last known hash) since nothing in-process remembers it anymore.
+    pub fn verify(&self) -> io::Result<VerifyResult> {
+        let state = self.state.lock().unwrap();
+
+        if !self.path.exists() {
+            return Ok(VerifyResult { valid: true, broken_at: None, records_checked: 0 });
+        }
+
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut expected_prev_hash = GENESIS_HASH.to_string();
+        let mut records_checked = 0u64;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: Value = match serde_json::from_str(&line) {
+                Ok(v) => v,
+                Err(_) => return Ok(Self::broken(index)),
+            };
+
+            let seq = record["seq"].as_u64().unwrap_or(0);
+            let timestamp_ms = record["timestamp_ms"].as_u64().unwrap_or(0) as u128;
+            let recorded_prev_hash = record["prev_hash"].as_str().unwrap_or("").to_string();
+            let recorded_hash = record["hash"].as_str().unwrap_or("").to_string();
+
+            if recorded_prev_hash != expected_prev_hash {
+                return Ok(Self::broken(index));
+            }
+
+            let expected_hash = Self::compute_hash(&recorded_prev_hash, seq, timestamp_ms, &record["payload"]);
+            if expected_hash != recorded_hash {
+                return Ok(Self::broken(index));
+            }
+
+            expected_prev_hash = recorded_hash;
+            records_checked += 1;
+        }
+
+        if expected_prev_hash != state.prev_hash {
+            return Ok(Self::broken(records_checked as usize));
+        }
+
+        Ok(VerifyResult { valid: true, broken_at: None, records_checked })
+    }
+
+    fn broken(index: usize) -> VerifyResult {
+        VerifyResult { valid: false, broken_at: Some(index as u64), records_checked: index as u64 }
+    }
+
+    fn compute_hash(prev_hash: &str, seq: u64, timestamp_ms: u128, payload: &Value) -> String {
+        // serde_json objects serialize with sorted keys by default (no
+        // `preserve_order` feature), so this doubles as the canonical form.
+        let canonical_payload = serde_json::to_string(payload).unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        hasher.update(prev_hash.as_bytes());
+        hasher.update(seq.to_string().as_bytes());
+        hasher.update(timestamp_ms.to_string().as_bytes());
+        hasher.update(canonical_payload.as_bytes());
+        to_hex(&hasher.finalize())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_log_path() -> PathBuf {
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("session-log-test-{}-{}", std::process::id(), id))
+    }
+
+    #[test]
+    fn fresh_log_verifies_empty_and_valid() {
+        let log = SessionLog::open(temp_log_path()).unwrap();
+        let result = log.verify().unwrap();
+        assert!(result.valid);
+        assert_eq!(result.broken_at, None);
+        assert_eq!(result.records_checked, 0);
+    }
+
+    #[test]
+    fn appended_records_verify_as_valid() {
+        let log = SessionLog::open(temp_log_path()).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "ls"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "pwd"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "whoami"})).unwrap();
+
+        let result = log.verify().unwrap();
+        assert!(result.valid);
+        assert_eq!(result.broken_at, None);
+        assert_eq!(result.records_checked, 3);
+    }
+
+    #[test]
+    fn tampering_with_a_record_breaks_the_chain_from_that_line() {
+        let path = temp_log_path();
+        let log = SessionLog::open(path.clone()).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "ls"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "pwd"})).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+        let mut record: Value = serde_json::from_str(&lines[0]).unwrap();
+        record["payload"]["message"] = serde_json::json!("rm -rf /");
+        lines[0] = record.to_string();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let result = SessionLog::open(path).unwrap().verify().unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.broken_at, Some(0));
+    }
+
+    #[test]
+    fn truncation_while_the_process_is_still_alive_breaks_the_chain() {
+        let path = temp_log_path();
+        let log = SessionLog::open(path.clone()).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "ls"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "pwd"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "whoami"})).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        std::fs::write(&path, lines[..2].join("\n") + "\n").unwrap();
+
+        // `log`'s in-memory ChainState still remembers the hash of the
+        // record that just got deleted from disk, so verify() on the same
+        // instance catches the cut even though the remaining lines chain
+        // cleanly on their own.
+        let result = log.verify().unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.broken_at, Some(2));
+    }
+
+    #[test]
+    fn truncation_across_a_fresh_reopen_is_not_detectable_without_an_external_anchor() {
+        let path = temp_log_path();
+        let log = SessionLog::open(path.clone()).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "ls"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "pwd"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "whoami"})).unwrap();
+        drop(log);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        std::fs::write(&path, lines[..2].join("\n") + "\n").unwrap();
+
+        // Once the original process (and its in-memory ChainState) is gone,
+        // re-opening derives state fresh from the (now-truncated) tail, so
+        // there's nothing left in-process to notice the cut.
+        let result = SessionLog::open(path).unwrap().verify().unwrap();
+        assert!(result.valid);
+        assert_eq!(result.records_checked, 2);
+    }
+
+    #[test]
+    fn reordered_lines_break_the_chain() {
+        let path = temp_log_path();
+        let log = SessionLog::open(path.clone()).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "ls"})).unwrap();
+        log.append(serde_json::json!({"type": "command", "message": "pwd"})).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<&str> = contents.lines().collect();
+        lines.swap(0, 1);
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let result = SessionLog::open(path).unwrap().verify().unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.broken_at, Some(0));
+    }
+}
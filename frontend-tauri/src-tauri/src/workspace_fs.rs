@@ -0,0 +1,311 @@
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Typed failure modes for workspace filesystem operations, surfaced to the
+/// frontend instead of ad-hoc stringly-typed errors.
+#[derive(Debug)]
+pub enum FsError {
+    NotFound,
+    IsDirectory,
+    NotADirectory,
+    InvalidPath,
+    AccessDenied,
+    AlreadyExists,
+    Io(io::Error),
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsError::NotFound => write!(f, "No such file or directory"),
+            FsError::IsDirectory => write!(f, "Path is a directory"),
+            FsError::NotADirectory => write!(f, "Path is not a directory"),
+            FsError::InvalidPath => write!(f, "Invalid path"),
+            FsError::AccessDenied => write!(f, "Access denied"),
+            FsError::AlreadyExists => write!(f, "Path already exists"),
+            FsError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FsError {}
+
+/// A single entry returned by [`WorkspaceFs::list`].
+#[derive(Debug, Serialize)]
+pub struct FileEntry {
+    pub name: String,
+    /// Path relative to the workspace root, using `/` separators.
+    pub path: String,
+    pub is_dir: bool,
+}
+
+/// A filesystem rooted at a fixed, canonicalized directory. Every operation
+/// resolves its caller-supplied relative path against that root and refuses
+/// to touch disk if the canonicalized result would fall outside it, so
+/// `../../etc/passwd`-style traversal (and symlinks that point outside the
+/// workspace) can't escape the sandbox.
+pub struct WorkspaceFs {
+    root: PathBuf,
+}
+
+impl WorkspaceFs {
+    pub fn new(root: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&root)?;
+        Ok(WorkspaceFs { root: root.canonicalize()? })
+    }
+
+    pub fn list(&self, dir: &str) -> Result<Vec<FileEntry>, FsError> {
+        let resolved = self.resolve_existing(dir)?;
+        if !resolved.is_dir() {
+            return Err(FsError::NotADirectory);
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&resolved).map_err(FsError::Io)? {
+            let entry = entry.map_err(FsError::Io)?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            let relative = path
+                .strip_prefix(&self.root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            entries.push(FileEntry { name, path: relative, is_dir: path.is_dir() });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    pub fn read(&self, path: &str) -> Result<String, FsError> {
+        let resolved = self.resolve_existing(path)?;
+        if resolved.is_dir() {
+            return Err(FsError::IsDirectory);
+        }
+        fs::read_to_string(resolved).map_err(FsError::Io)
+    }
+
+    pub fn write(&self, path: &str, content: &str) -> Result<(), FsError> {
+        let resolved = self.resolve_new(path)?;
+        if resolved.is_dir() {
+            return Err(FsError::IsDirectory);
+        }
+        fs::write(resolved, content).map_err(FsError::Io)
+    }
+
+    pub fn create(&self, path: &str) -> Result<(), FsError> {
+        let resolved = self.resolve_new(path)?;
+        if resolved.exists() {
+            return Err(FsError::AlreadyExists);
+        }
+        fs::write(resolved, "").map_err(FsError::Io)
+    }
+
+    pub fn mkdir(&self, path: &str) -> Result<(), FsError> {
+        let resolved = self.resolve_for_mkdir(path)?;
+        if resolved.exists() {
+            return Err(FsError::AlreadyExists);
+        }
+        fs::create_dir_all(resolved).map_err(FsError::Io)
+    }
+
+    pub fn remove(&self, path: &str) -> Result<(), FsError> {
+        let resolved = self.resolve_existing(path)?;
+        if resolved == self.root {
+            return Err(FsError::AccessDenied);
+        }
+        if resolved.is_dir() {
+            fs::remove_dir_all(resolved).map_err(FsError::Io)
+        } else {
+            fs::remove_file(resolved).map_err(FsError::Io)
+        }
+    }
+
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), FsError> {
+        let from_resolved = self.resolve_existing(from)?;
+        if from_resolved == self.root {
+            return Err(FsError::AccessDenied);
+        }
+        let to_resolved = self.resolve_new(to)?;
+        if to_resolved.exists() {
+            return Err(FsError::AlreadyExists);
+        }
+        fs::rename(from_resolved, to_resolved).map_err(FsError::Io)
+    }
+
+    /// Resolves a path that must already exist, canonicalizing the full
+    /// path so symlinks can't be used to escape the root either.
+    fn resolve_existing(&self, relative: &str) -> Result<PathBuf, FsError> {
+        if relative.is_empty() {
+            return Ok(self.root.clone());
+        }
+        let candidate = self.root.join(Self::sanitize(relative)?);
+        let canonical = candidate.canonicalize().map_err(|_| FsError::NotFound)?;
+        if !canonical.starts_with(&self.root) {
+            return Err(FsError::AccessDenied);
+        }
+        Ok(canonical)
+    }
+
+    /// Resolves a path that may not exist yet (write/create targets,
+    /// rename destinations), by canonicalizing the nearest existing
+    /// ancestor - its parent directory - and re-appending the file name.
+    fn resolve_new(&self, relative: &str) -> Result<PathBuf, FsError> {
+        let candidate = self.root.join(Self::sanitize(relative)?);
+        let parent = candidate.parent().ok_or(FsError::InvalidPath)?;
+        let canonical_parent = parent.canonicalize().map_err(|_| FsError::NotFound)?;
+        if !canonical_parent.starts_with(&self.root) {
+            return Err(FsError::AccessDenied);
+        }
+        let file_name = candidate.file_name().ok_or(FsError::InvalidPath)?;
+        let resolved = canonical_parent.join(file_name);
+
+        // The parent being inside the root doesn't guarantee the leaf is:
+        // if it already exists as a symlink, it can point anywhere. Follow
+        // and canonicalize it too, and refuse to touch it if that escapes
+        // the root - otherwise `write`/`create` would happily write through
+        // a symlink (even a dangling one whose target doesn't exist yet).
+        if fs::symlink_metadata(&resolved).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+            match resolved.canonicalize() {
+                Ok(canonical_leaf) if canonical_leaf.starts_with(&self.root) => {}
+                _ => return Err(FsError::AccessDenied),
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Like [`Self::resolve_new`], but for `mkdir`, which (via
+    /// `create_dir_all`) is allowed to create more than one missing path
+    /// segment at once. Walks up from the candidate to the nearest existing
+    /// ancestor, canonicalizes *that*, verifies it's inside the root, then
+    /// re-appends the not-yet-created remainder.
+    fn resolve_for_mkdir(&self, relative: &str) -> Result<PathBuf, FsError> {
+        let candidate = self.root.join(Self::sanitize(relative)?);
+
+        let mut ancestor: &Path = &candidate;
+        while !ancestor.exists() {
+            ancestor = ancestor.parent().ok_or(FsError::InvalidPath)?;
+        }
+
+        let canonical_ancestor = ancestor.canonicalize().map_err(|_| FsError::NotFound)?;
+        if !canonical_ancestor.starts_with(&self.root) {
+            return Err(FsError::AccessDenied);
+        }
+
+        let remainder = candidate.strip_prefix(ancestor).unwrap_or_else(|_| Path::new(""));
+        Ok(canonical_ancestor.join(remainder))
+    }
+
+    fn sanitize(relative: &str) -> Result<PathBuf, FsError> {
+        let path = Path::new(relative);
+        if path.is_absolute() || path.components().any(|c| matches!(c, Component::ParentDir)) {
+            return Err(FsError::InvalidPath);
+        }
+        Ok(path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_workspace() -> WorkspaceFs {
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let root = std::env::temp_dir().join(format!("workspace-fs-test-{}-{}", std::process::id(), id));
+        WorkspaceFs::new(root).unwrap()
+    }
+
+    #[test]
+    fn parent_traversal_is_rejected() {
+        let fs = temp_workspace();
+        assert!(matches!(fs.read("../etc/passwd"), Err(FsError::InvalidPath)));
+        assert!(matches!(fs.write("../escape.txt", "x"), Err(FsError::InvalidPath)));
+    }
+
+    #[test]
+    fn write_and_read_round_trip() {
+        let fs = temp_workspace();
+        fs.write("notes.txt", "hello").unwrap();
+        assert_eq!(fs.read("notes.txt").unwrap(), "hello");
+    }
+
+    #[test]
+    fn write_refuses_to_follow_a_symlink_escaping_the_root() {
+        let fs = temp_workspace();
+        let outside_target = std::env::temp_dir().join(format!(
+            "workspace-fs-test-outside-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let link_path = fs.root.join("escape_link");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside_target, &link_path).unwrap();
+
+        let result = fs.write("escape_link", "pwned");
+        assert!(matches!(result, Err(FsError::AccessDenied)));
+        assert!(!outside_target.exists());
+    }
+
+    #[test]
+    fn mkdir_creates_missing_nested_directories() {
+        let fs = temp_workspace();
+        fs.mkdir("a/b/c").unwrap();
+        assert!(fs.root.join("a/b/c").is_dir());
+    }
+
+    #[test]
+    fn mkdir_rejects_creating_outside_root_via_ancestor_symlink() {
+        let fs = temp_workspace();
+        let outside_target = std::env::temp_dir().join(format!(
+            "workspace-fs-test-outside-dir-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&outside_target).unwrap();
+        let link_path = fs.root.join("escape_dir");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside_target, &link_path).unwrap();
+
+        let result = fs.mkdir("escape_dir/new_child");
+        assert!(matches!(result, Err(FsError::AccessDenied)));
+        assert!(!outside_target.join("new_child").exists());
+    }
+
+    #[test]
+    fn remove_rejects_the_workspace_root() {
+        let fs = temp_workspace();
+        assert!(matches!(fs.remove(""), Err(FsError::AccessDenied)));
+    }
+
+    #[test]
+    fn rename_rejects_the_workspace_root() {
+        let fs = temp_workspace();
+        assert!(matches!(fs.rename("", "somewhere_else"), Err(FsError::AccessDenied)));
+    }
+
+    #[test]
+    fn rename_moves_an_existing_file() {
+        let fs = temp_workspace();
+        fs.write("old.txt", "data").unwrap();
+        fs.rename("old.txt", "new.txt").unwrap();
+        assert_eq!(fs.read("new.txt").unwrap(), "data");
+        assert!(matches!(fs.read("old.txt"), Err(FsError::NotFound)));
+    }
+
+    #[test]
+    fn rename_refuses_to_overwrite_an_existing_destination() {
+        let fs = temp_workspace();
+        fs.write("a.txt", "a").unwrap();
+        fs.write("b.txt", "b").unwrap();
+        assert!(matches!(fs.rename("a.txt", "b.txt"), Err(FsError::AlreadyExists)));
+    }
+}